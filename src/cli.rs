@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::process::exit;
+use std::time::Duration;
 
 use clap::Parser;
 use clap::Subcommand;
@@ -27,8 +28,13 @@ use crate::cmd::orphans::delete_orphans;
 use crate::cmd::orphans::list_orphans;
 use crate::cmd::stats::StatsFormat;
 use crate::cmd::stats::print_stats;
+use crate::cmd::sync::pull_state;
+use crate::cmd::sync::push_state;
+use crate::cmd::sync::sync_status;
 use crate::collection::resolve_directory;
 use crate::config::load_config;
+use crate::config::resolve_drill_profile;
+use crate::error::ErrorReport;
 use crate::error::Fallible;
 use crate::types::timestamp::Timestamp;
 use crate::utils::wait_for_server;
@@ -40,6 +46,9 @@ enum Command {
     Drill {
         /// Path to the collection directory. By default, the current working directory is used.
         directory: Option<String>,
+        /// Named profile from `hashcards.toml` to layer over the base `[drill]` settings.
+        #[arg(long)]
+        profile: Option<String>,
         /// Maximum number of cards to drill in a session. By default, all cards due today are drilled.
         #[arg(long)]
         card_limit: Option<usize>,
@@ -64,6 +73,15 @@ enum Command {
         /// Whether or not to bury siblings. Default is true.
         #[arg(long)]
         bury_siblings: Option<bool>,
+        /// Username for HTTP Basic Auth. Requires `auth_password` to also be set.
+        #[arg(long)]
+        auth_user: Option<String>,
+        /// Password for HTTP Basic Auth. Requires `auth_user` to also be set.
+        #[arg(long)]
+        auth_password: Option<String>,
+        /// Wall-clock limit for the session, e.g. "25m", "1h30m", "90s". By default, a session has no time limit.
+        #[arg(long)]
+        time_limit: Option<String>,
     },
     /// Check the integrity of a collection.
     Check {
@@ -74,7 +92,7 @@ enum Command {
     Stats {
         /// Path to the collection directory. By default, the current working directory is used.
         directory: Option<String>,
-        /// Which output format to use.
+        /// Which output format to use: html (default), json, or csv.
         #[arg(long, default_value_t = StatsFormat::Html)]
         format: StatsFormat,
     },
@@ -91,6 +109,11 @@ enum Command {
         #[arg(long)]
         output: Option<String>,
     },
+    /// Synchronize review state with a remote hashcards server.
+    Sync {
+        #[command(subcommand)]
+        command: SyncCommand,
+    },
 }
 
 #[derive(Subcommand)]
@@ -107,11 +130,71 @@ enum OrphanCommand {
     },
 }
 
+#[derive(Subcommand)]
+enum SyncCommand {
+    /// Upload the whole local review state to the remote in a single request. Records are
+    /// not compared with the remote's first, so remote records are replaced whatever their age.
+    Push {
+        /// Path to the collection directory. By default, the current working directory is used.
+        directory: Option<String>,
+    },
+    /// Download remote review state and merge it into the local database, keeping
+    /// whichever side reviewed each card most recently.
+    Pull {
+        /// Path to the collection directory. By default, the current working directory is used.
+        directory: Option<String>,
+    },
+    /// Report how many cards differ between the local and remote state without writing.
+    Status {
+        /// Path to the collection directory. By default, the current working directory is used.
+        directory: Option<String>,
+    },
+}
+
+/// Parses a compact duration string like `"25m"`, `"1h30m"`, or `"90s"` into a
+/// [`Duration`], accumulating each `<integer><unit>` pair it finds (`h`, `m`, `s`).
+fn parse_duration(input: &str) -> Fallible<Duration> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ErrorReport::new("time limit must not be empty".to_string()));
+    }
+    let mut total_secs: u64 = 0;
+    let mut digits = String::new();
+    for ch in input.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        if digits.is_empty() {
+            return Err(ErrorReport::new(format!("invalid time limit: {input}")));
+        }
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| ErrorReport::new(format!("invalid time limit: {input}")))?;
+        digits.clear();
+        let secs = match ch {
+            'h' => value.checked_mul(3600),
+            'm' => value.checked_mul(60),
+            's' => Some(value),
+            _ => return Err(ErrorReport::new(format!("invalid time limit: {input}"))),
+        }
+        .ok_or_else(|| ErrorReport::new(format!("time limit out of range: {input}")))?;
+        total_secs = total_secs
+            .checked_add(secs)
+            .ok_or_else(|| ErrorReport::new(format!("time limit out of range: {input}")))?;
+    }
+    if !digits.is_empty() {
+        return Err(ErrorReport::new(format!("invalid time limit: {input}")));
+    }
+    Ok(Duration::from_secs(total_secs))
+}
+
 pub async fn entrypoint() -> Fallible<()> {
     let cli: Command = Command::parse();
     match cli {
         Command::Drill {
             directory,
+            profile,
             card_limit,
             new_card_limit,
             host,
@@ -120,13 +203,16 @@ pub async fn entrypoint() -> Fallible<()> {
             open_browser,
             answer_controls,
             bury_siblings,
+            auth_user,
+            auth_password,
+            time_limit,
         } => {
             // Resolve directory and load config file.
             let resolved_dir = resolve_directory(directory)?;
             let file_config = load_config(&resolved_dir)?;
-            let dc = file_config.drill;
+            let dc = resolve_drill_profile(file_config, profile.as_deref())?;
 
-            // Merge: CLI arg > config file > hardcoded default.
+            // Merge: CLI arg > selected profile > base config file > hardcoded default.
             let host = host
                 .or(dc.host)
                 .unwrap_or_else(|| "127.0.0.1".to_string());
@@ -135,6 +221,17 @@ pub async fn entrypoint() -> Fallible<()> {
             let new_card_limit = new_card_limit.or(dc.new_card_limit);
             let open_browser = open_browser.or(dc.open_browser).unwrap_or(true);
             let bury_siblings = bury_siblings.or(dc.bury_siblings).unwrap_or(true);
+            let auth_user = auth_user.or(dc.auth_user);
+            let auth_password = auth_password.or(dc.auth_password);
+            if auth_user.is_some() != auth_password.is_some() {
+                return Err(ErrorReport::new(
+                    "--auth-user and --auth-password must both be set, or neither".to_string(),
+                ));
+            }
+            let time_limit = time_limit
+                .or(dc.time_limit)
+                .map(|s| parse_duration(&s))
+                .transpose()?;
             let answer_controls = answer_controls
                 .or_else(|| {
                     dc.answer_controls.as_deref().and_then(|s| match s {
@@ -145,6 +242,12 @@ pub async fn entrypoint() -> Fallible<()> {
                 })
                 .unwrap_or(AnswerControls::Full);
 
+            if host != "127.0.0.1" && host != "localhost" && auth_user.is_none() && auth_password.is_none() {
+                eprintln!(
+                    "Warning: the drill server is bound to {host}, which is reachable from other machines on your network, but no --auth-user/--auth-password is configured."
+                );
+            }
+
             if open_browser {
                 // Start a separate task to open the browser once the server is up.
                 let browser_host = host.clone();
@@ -171,6 +274,9 @@ pub async fn entrypoint() -> Fallible<()> {
                 shuffle: true,
                 answer_controls,
                 bury_siblings,
+                auth_user,
+                auth_password,
+                time_limit,
             };
             start_server(config).await
         }
@@ -181,5 +287,58 @@ pub async fn entrypoint() -> Fallible<()> {
             OrphanCommand::Delete { directory } => delete_orphans(directory),
         },
         Command::Export { directory, output } => export_collection(directory, output),
+        Command::Sync { command } => match command {
+            SyncCommand::Push { directory } => push_state(directory).await,
+            SyncCommand::Pull { directory } => pull_state(directory).await,
+            SyncCommand::Status { directory } => sync_status(directory).await,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_accepts_a_single_unit() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("25m").unwrap(), Duration::from_secs(25 * 60));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(2 * 3600));
+    }
+
+    #[test]
+    fn parse_duration_accumulates_multiple_units() {
+        assert_eq!(
+            parse_duration("1h30m").unwrap(),
+            Duration::from_secs(3600 + 30 * 60)
+        );
+    }
+
+    #[test]
+    fn parse_duration_rejects_empty_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("   ").is_err());
+    }
+
+    #[test]
+    fn parse_duration_rejects_malformed_input() {
+        assert!(parse_duration("abc").is_err());
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("h").is_err());
+    }
+
+    #[test]
+    fn parse_duration_rejects_overflow() {
+        assert!(parse_duration("99999999999999999999h").is_err());
+    }
+
+    #[test]
+    fn parse_duration_rejects_multiply_and_add_overflow() {
+        // Parses fine as a u64, but value * 3600 overflows before any Duration exists.
+        assert!(parse_duration("9999999999999999h").is_err());
+        assert!(parse_duration("18446744073709551615h1s").is_err());
+        // Each pair fits in a u64, but their sum does not.
+        assert!(parse_duration("18446744073709551615s1s").is_err());
     }
 }
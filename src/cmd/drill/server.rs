@@ -0,0 +1,223 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::Request;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::header::AUTHORIZATION;
+use axum::http::header::WWW_AUTHENTICATE;
+use axum::middleware;
+use axum::middleware::Next;
+use axum::response::Html;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::get;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+
+use crate::error::ErrorReport;
+use crate::error::Fallible;
+use crate::types::timestamp::Timestamp;
+
+/// Which answer controls to present in the drill UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AnswerControls {
+    /// The full again/hard/good/easy grading scale.
+    Full,
+    /// A simplified pass/fail scale.
+    Binary,
+}
+
+#[derive(Clone)]
+pub struct ServerConfig {
+    pub directory: Option<String>,
+    pub host: String,
+    pub port: u16,
+    pub session_started_at: Timestamp,
+    pub card_limit: Option<usize>,
+    pub new_card_limit: Option<usize>,
+    pub deck_filter: Option<String>,
+    pub shuffle: bool,
+    pub answer_controls: AnswerControls,
+    pub bury_siblings: bool,
+    /// Username for HTTP Basic Auth. Only enforced when `auth_password` is also set.
+    pub auth_user: Option<String>,
+    /// Password for HTTP Basic Auth. Only enforced when `auth_user` is also set.
+    pub auth_password: Option<String>,
+    /// Wall-clock limit for the session. `None` means the session never expires.
+    pub time_limit: Option<Duration>,
+}
+
+impl fmt::Debug for ServerConfig {
+    /// Manual impl so `auth_password` never leaks into debug/log output.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerConfig")
+            .field("directory", &self.directory)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("session_started_at", &self.session_started_at)
+            .field("card_limit", &self.card_limit)
+            .field("new_card_limit", &self.new_card_limit)
+            .field("deck_filter", &self.deck_filter)
+            .field("shuffle", &self.shuffle)
+            .field("answer_controls", &self.answer_controls)
+            .field("bury_siblings", &self.bury_siblings)
+            .field("auth_user", &self.auth_user)
+            .field("auth_password", &self.auth_password.as_ref().map(|_| "[redacted]"))
+            .field("time_limit", &self.time_limit)
+            .finish()
+    }
+}
+
+impl ServerConfig {
+    fn credentials(&self) -> Option<(&str, &str)> {
+        match (&self.auth_user, &self.auth_password) {
+            (Some(user), Some(password)) => Some((user.as_str(), password.as_str())),
+            _ => None,
+        }
+    }
+
+    /// Whether the session's wall-clock time limit, if any, has elapsed.
+    fn is_out_of_time(&self) -> bool {
+        match self.time_limit {
+            Some(limit) => Timestamp::now() - self.session_started_at >= limit,
+            None => false,
+        }
+    }
+}
+
+const OUT_OF_TIME_HTML: &str = "<html><body><h1>Out of time</h1><p>This session's time limit has been reached. Nice work!</p></body></html>";
+
+async fn next_card(State(config): State<Arc<ServerConfig>>) -> Response {
+    if config.is_out_of_time() {
+        return Html(OUT_OF_TIME_HTML).into_response();
+    }
+    // Returns a placeholder page: no card is selected here, and `card_limit`,
+    // `new_card_limit`, `deck_filter`, `shuffle` and `bury_siblings` are not consulted.
+    Html("<html><body><!-- next due card --></body></html>").into_response()
+}
+
+async fn answer_controls(State(config): State<Arc<ServerConfig>>) -> Response {
+    match config.answer_controls {
+        AnswerControls::Full => Html("<div class=\"controls-full\"></div>").into_response(),
+        AnswerControls::Binary => Html("<div class=\"controls-binary\"></div>").into_response(),
+    }
+}
+
+/// Rejects requests with `401` unless they present HTTP Basic credentials matching
+/// `config.auth_user`/`config.auth_password`, comparing in constant time.
+async fn require_basic_auth(
+    State(config): State<Arc<ServerConfig>>,
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some((expected_user, expected_password)) = config.credentials() else {
+        return next.run(request).await;
+    };
+
+    let authorized = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|encoded| BASE64_STANDARD.decode(encoded).ok())
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+        .and_then(|decoded| decoded.split_once(':').map(|(u, p)| (u.to_string(), p.to_string())))
+        .is_some_and(|(user, password)| {
+            let user_ok = constant_time_eq(user.as_bytes(), expected_user.as_bytes());
+            let password_ok = constant_time_eq(password.as_bytes(), expected_password.as_bytes());
+            user_ok & password_ok
+        });
+
+    if authorized {
+        next.run(request).await
+    } else {
+        let mut response = StatusCode::UNAUTHORIZED.into_response();
+        response
+            .headers_mut()
+            .insert(WWW_AUTHENTICATE, "Basic realm=\"hashcards\"".parse().unwrap());
+        response
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch, so the
+/// time taken doesn't leak how many leading bytes matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+pub async fn start_server(config: ServerConfig) -> Fallible<()> {
+    let host = config.host.clone();
+    let port = config.port;
+    let state = Arc::new(config);
+
+    let mut app = Router::new()
+        .route("/", get(next_card))
+        .route("/api/card", get(next_card))
+        .route("/api/answer-controls", get(answer_controls))
+        .with_state(state.clone());
+
+    if state.credentials().is_some() {
+        app = app.layer(middleware::from_fn_with_state(state.clone(), require_basic_auth));
+    }
+
+    let listener = tokio::net::TcpListener::bind((host.as_str(), port))
+        .await
+        .map_err(|e| ErrorReport::new(format!("failed to bind {host}:{port}: {e}")))?;
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| ErrorReport::new(format!("server error: {e}")))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_config_debug_redacts_auth_password() {
+        let config = ServerConfig {
+            directory: None,
+            host: "127.0.0.1".to_string(),
+            port: 8000,
+            session_started_at: Timestamp::now(),
+            card_limit: None,
+            new_card_limit: None,
+            deck_filter: None,
+            shuffle: false,
+            answer_controls: AnswerControls::Full,
+            bury_siblings: true,
+            auth_user: Some("alice".to_string()),
+            auth_password: Some("hunter2".to_string()),
+            time_limit: None,
+        };
+        let debug = format!("{config:?}");
+        assert!(debug.contains("alice"));
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("[redacted]"));
+    }
+}
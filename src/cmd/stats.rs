@@ -0,0 +1,226 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::collection::resolve_directory;
+use crate::collection::scan_review_state;
+use crate::error::ErrorReport;
+use crate::error::Fallible;
+
+/// Which shape to print collection statistics in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatsFormat {
+    /// A human-readable HTML report (the default).
+    Html,
+    /// A single JSON object keyed by deck, plus a top-level `totals` object.
+    Json,
+    /// One CSV row per deck, with a header line and a final `total` row.
+    Csv,
+}
+
+impl fmt::Display for StatsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            StatsFormat::Html => "html",
+            StatsFormat::Json => "json",
+            StatsFormat::Csv => "csv",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Due/maturity/retention counts for a single deck (or the collection as a whole).
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct DeckStats {
+    pub due: usize,
+    pub new: usize,
+    pub learning: usize,
+    pub young: usize,
+    pub mature: usize,
+    /// Fraction of reviews on mature cards that were graded as a pass, in `[0, 1]`.
+    pub retention: f64,
+}
+
+impl DeckStats {
+    fn merge(&mut self, other: &DeckStats) {
+        self.due += other.due;
+        self.new += other.new;
+        self.learning += other.learning;
+        self.young += other.young;
+        self.mature += other.mature;
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct StatsReport {
+    #[serde(flatten)]
+    decks: BTreeMap<String, DeckStats>,
+    totals: DeckStats,
+}
+
+/// Fraction of `reviews` graded as a pass, in `[0, 1]`. `0.0` when there were no reviews.
+fn retention(passes: usize, reviews: usize) -> f64 {
+    if reviews > 0 {
+        passes as f64 / reviews as f64
+    } else {
+        0.0
+    }
+}
+
+fn gather_stats(directory: &std::path::Path) -> Fallible<StatsReport> {
+    let state = scan_review_state(directory)?;
+    let mut report = StatsReport::default();
+
+    // Per-deck mature (reviews, passes), accumulated alongside the totals' same counts.
+    let mut mature_by_deck: HashMap<String, (usize, usize)> = HashMap::new();
+    let mut mature_passes = 0usize;
+    let mut mature_reviews = 0usize;
+    for card in &state.cards {
+        let deck_stats = report.decks.entry(card.deck.clone()).or_default();
+        if card.is_due {
+            deck_stats.due += 1;
+        }
+        match card.maturity {
+            crate::collection::Maturity::New => deck_stats.new += 1,
+            crate::collection::Maturity::Learning => deck_stats.learning += 1,
+            crate::collection::Maturity::Young => deck_stats.young += 1,
+            crate::collection::Maturity::Mature => {
+                deck_stats.mature += 1;
+                mature_reviews += card.review_count;
+                mature_passes += card.pass_count;
+                let deck_mature = mature_by_deck.entry(card.deck.clone()).or_default();
+                deck_mature.0 += card.review_count;
+                deck_mature.1 += card.pass_count;
+            }
+        }
+    }
+
+    for (deck, deck_stats) in report.decks.iter_mut() {
+        let (reviews, passes) = mature_by_deck.get(deck).copied().unwrap_or_default();
+        deck_stats.retention = retention(passes, reviews);
+    }
+    for deck_stats in report.decks.values() {
+        report.totals.merge(deck_stats);
+    }
+    report.totals.retention = retention(mature_passes, mature_reviews);
+
+    Ok(report)
+}
+
+pub fn print_stats(directory: Option<String>, format: StatsFormat) -> Fallible<()> {
+    let resolved_dir = resolve_directory(directory)?;
+    let report = gather_stats(&resolved_dir)?;
+
+    match format {
+        StatsFormat::Html => print_html(&report),
+        StatsFormat::Json => print_json(&report),
+        StatsFormat::Csv => print_csv(&report),
+    }
+}
+
+fn print_html(report: &StatsReport) -> Fallible<()> {
+    println!("<html><body><h1>hashcards statistics</h1><table>");
+    println!("<tr><th>deck</th><th>due</th><th>new</th><th>learning</th><th>young</th><th>mature</th></tr>");
+    for (deck, stats) in &report.decks {
+        println!(
+            "<tr><td>{deck}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            stats.due, stats.new, stats.learning, stats.young, stats.mature
+        );
+    }
+    println!(
+        "<tr><td><strong>total</strong></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+        report.totals.due, report.totals.new, report.totals.learning, report.totals.young, report.totals.mature
+    );
+    println!("</table><p>retention: {:.1}%</p></body></html>", report.totals.retention * 100.0);
+    Ok(())
+}
+
+fn print_json(report: &StatsReport) -> Fallible<()> {
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| ErrorReport::new(format!("failed to serialize stats as JSON: {e}")))?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Quotes `field` per RFC 4180 if it contains a comma, quote, or newline, doubling any
+/// embedded quotes. Leaves plain fields untouched.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn print_csv(report: &StatsReport) -> Fallible<()> {
+    println!("deck,due,new,learning,young,mature,retention");
+    for (deck, stats) in &report.decks {
+        let deck = csv_field(deck);
+        println!(
+            "{deck},{},{},{},{},{},{:.4}",
+            stats.due, stats.new, stats.learning, stats.young, stats.mature, stats.retention
+        );
+    }
+    println!(
+        "total,{},{},{},{},{},{:.4}",
+        report.totals.due,
+        report.totals.new,
+        report.totals.learning,
+        report.totals.young,
+        report.totals.mature,
+        report.totals.retention
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_report_serializes_with_deck_names_at_the_top_level() {
+        let mut report = StatsReport::default();
+        report.decks.insert("Kanji".to_string(), DeckStats::default());
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json.get("Kanji").is_some());
+        assert!(json.get("totals").is_some());
+        assert!(json.get("decks").is_none());
+    }
+
+    #[test]
+    fn retention_is_zero_with_no_reviews() {
+        assert_eq!(retention(0, 0), 0.0);
+    }
+
+    #[test]
+    fn retention_divides_passes_by_reviews() {
+        assert_eq!(retention(3, 4), 0.75);
+    }
+
+    #[test]
+    fn csv_field_passes_through_plain_text() {
+        assert_eq!(csv_field("Kanji"), "Kanji");
+    }
+
+    #[test]
+    fn csv_field_quotes_and_escapes_commas_and_quotes() {
+        assert_eq!(csv_field("Kanji, N5"), "\"Kanji, N5\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
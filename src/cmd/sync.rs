@@ -0,0 +1,241 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::collection::load_review_state;
+use crate::collection::resolve_directory;
+use crate::collection::save_review_state;
+use crate::config::load_config;
+use crate::error::ErrorReport;
+use crate::error::Fallible;
+use crate::types::timestamp::Timestamp;
+
+/// A single card's scheduling state, as exchanged with a remote hashcards server.
+/// Keyed by card hash on the wire (see [`RemoteState`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardState {
+    pub due: Timestamp,
+    pub last_reviewed: Timestamp,
+    pub reps: u32,
+    pub lapses: u32,
+}
+
+/// The full review state of a collection, keyed by card hash.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RemoteState(pub HashMap<String, CardState>);
+
+fn remote_config(directory: &std::path::Path) -> Fallible<(String, Option<String>)> {
+    let config = load_config(directory)?;
+    let remote = config
+        .remote
+        .ok_or_else(|| ErrorReport::new("no [remote] section configured in hashcards.toml".to_string()))?;
+    let url = remote
+        .url
+        .clone()
+        .ok_or_else(|| ErrorReport::new("[remote] section is missing a url".to_string()))?;
+    Ok((url, remote.resolve_token()))
+}
+
+fn authorized_request(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    url: &str,
+    token: Option<&str>,
+) -> reqwest::RequestBuilder {
+    let request = client.request(method, url);
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+/// Merges `remote` into `local`, keeping, per card hash, whichever side reviewed the
+/// card most recently (last-writer-wins on [`Timestamp`]).
+fn merge_states(local: &RemoteState, remote: &RemoteState) -> RemoteState {
+    let mut merged = local.0.clone();
+    for (hash, remote_card) in &remote.0 {
+        match merged.get(hash) {
+            Some(local_card) if local_card.last_reviewed >= remote_card.last_reviewed => {}
+            _ => {
+                merged.insert(hash.clone(), remote_card.clone());
+            }
+        }
+    }
+    RemoteState(merged)
+}
+
+pub async fn push_state(directory: Option<String>) -> Fallible<()> {
+    let resolved_dir = resolve_directory(directory)?;
+    let (url, token) = remote_config(&resolved_dir)?;
+    let state = load_review_state(&resolved_dir)?;
+
+    let client = reqwest::Client::new();
+    let response = authorized_request(&client, reqwest::Method::PUT, &format!("{url}/state"), token.as_deref())
+        .json(&state)
+        .send()
+        .await
+        .map_err(|e| ErrorReport::new(format!("failed to push review state: {e}")))?;
+    if !response.status().is_success() {
+        return Err(ErrorReport::new(format!(
+            "remote rejected push with status {}",
+            response.status()
+        )));
+    }
+
+    println!("Pushed {} card(s) to {url}.", state.0.len());
+    Ok(())
+}
+
+pub async fn pull_state(directory: Option<String>) -> Fallible<()> {
+    let resolved_dir = resolve_directory(directory)?;
+    let (url, token) = remote_config(&resolved_dir)?;
+    let local = load_review_state(&resolved_dir)?;
+
+    let client = reqwest::Client::new();
+    let response = authorized_request(&client, reqwest::Method::GET, &format!("{url}/state"), token.as_deref())
+        .send()
+        .await
+        .map_err(|e| ErrorReport::new(format!("failed to pull review state: {e}")))?;
+    if !response.status().is_success() {
+        return Err(ErrorReport::new(format!(
+            "remote rejected pull with status {}",
+            response.status()
+        )));
+    }
+    let remote: RemoteState = response
+        .json()
+        .await
+        .map_err(|e| ErrorReport::new(format!("failed to parse remote review state: {e}")))?;
+
+    let merged = merge_states(&local, &remote);
+    let updated = merged.0.len().saturating_sub(local.0.len())
+        + merged
+            .0
+            .iter()
+            .filter(|(hash, card)| local.0.get(*hash).is_some_and(|l| l.last_reviewed < card.last_reviewed))
+            .count();
+    save_review_state(&resolved_dir, &merged)?;
+
+    println!("Pulled {updated} updated card(s) from {url}.");
+    Ok(())
+}
+
+pub async fn sync_status(directory: Option<String>) -> Fallible<()> {
+    let resolved_dir = resolve_directory(directory)?;
+    let (url, token) = remote_config(&resolved_dir)?;
+    let local = load_review_state(&resolved_dir)?;
+
+    let client = reqwest::Client::new();
+    let response = authorized_request(&client, reqwest::Method::GET, &format!("{url}/state"), token.as_deref())
+        .send()
+        .await
+        .map_err(|e| ErrorReport::new(format!("failed to fetch remote review state: {e}")))?;
+    if !response.status().is_success() {
+        return Err(ErrorReport::new(format!(
+            "remote rejected status check with status {}",
+            response.status()
+        )));
+    }
+    let remote: RemoteState = response
+        .json()
+        .await
+        .map_err(|e| ErrorReport::new(format!("failed to parse remote review state: {e}")))?;
+
+    let mut ahead = 0usize;
+    let mut behind = 0usize;
+    for (hash, local_card) in &local.0 {
+        match remote.0.get(hash) {
+            Some(remote_card) if local_card.last_reviewed > remote_card.last_reviewed => ahead += 1,
+            None => ahead += 1,
+            _ => {}
+        }
+    }
+    for (hash, remote_card) in &remote.0 {
+        match local.0.get(hash) {
+            Some(local_card) if remote_card.last_reviewed > local_card.last_reviewed => behind += 1,
+            None => behind += 1,
+            _ => {}
+        }
+    }
+
+    println!("{ahead} card(s) ahead of {url} (would be pushed).");
+    println!("{behind} card(s) behind {url} (would be pulled).");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(last_reviewed: Timestamp) -> CardState {
+        CardState {
+            due: last_reviewed,
+            last_reviewed,
+            reps: 1,
+            lapses: 0,
+        }
+    }
+
+    #[test]
+    fn merge_states_adds_cards_missing_locally() {
+        let local = RemoteState(HashMap::new());
+        let mut remote = HashMap::new();
+        remote.insert("a".to_string(), card(Timestamp::now()));
+        let merged = merge_states(&local, &RemoteState(remote));
+        assert!(merged.0.contains_key("a"));
+    }
+
+    #[test]
+    fn merge_states_keeps_the_local_card_on_a_tie() {
+        let now = Timestamp::now();
+        let local_card = card(now);
+        let mut remote_card = card(now);
+        remote_card.reps = 2;
+
+        let mut local = HashMap::new();
+        local.insert("a".to_string(), local_card.clone());
+        let mut remote = HashMap::new();
+        remote.insert("a".to_string(), remote_card);
+
+        let merged = merge_states(&RemoteState(local), &RemoteState(remote));
+        assert_eq!(merged.0["a"].reps, local_card.reps);
+    }
+
+    #[test]
+    fn merge_states_prefers_whichever_side_reviewed_more_recently() {
+        let older = Timestamp::now();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let newer = Timestamp::now();
+
+        let mut local = HashMap::new();
+        local.insert("a".to_string(), card(older));
+        let mut remote = HashMap::new();
+        remote.insert("a".to_string(), card(newer));
+
+        let merged = merge_states(&RemoteState(local), &RemoteState(remote));
+        assert_eq!(merged.0["a"].last_reviewed, newer);
+
+        let mut local = HashMap::new();
+        local.insert("a".to_string(), card(newer));
+        let mut remote = HashMap::new();
+        remote.insert("a".to_string(), card(older));
+
+        let merged = merge_states(&RemoteState(local), &RemoteState(remote));
+        assert_eq!(merged.0["a"].last_reviewed, newer);
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::fmt;
 use std::fs::read_to_string;
 use std::path::Path;
 
@@ -12,9 +14,11 @@ const CONFIG_FILENAME: &str = "hashcards.toml";
 #[serde(default)]
 pub struct Config {
     pub drill: DrillConfig,
+    pub profile: HashMap<String, DrillConfig>,
+    pub remote: Option<RemoteConfig>,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Default, Clone, Deserialize)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct DrillConfig {
     pub card_limit: Option<usize>,
@@ -24,6 +28,72 @@ pub struct DrillConfig {
     pub open_browser: Option<bool>,
     pub answer_controls: Option<String>,
     pub bury_siblings: Option<bool>,
+    pub auth_user: Option<String>,
+    pub auth_password: Option<String>,
+    pub time_limit: Option<String>,
+}
+
+impl fmt::Debug for DrillConfig {
+    /// Manual impl so `auth_password` never leaks into debug/log output.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DrillConfig")
+            .field("card_limit", &self.card_limit)
+            .field("new_card_limit", &self.new_card_limit)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("open_browser", &self.open_browser)
+            .field("answer_controls", &self.answer_controls)
+            .field("bury_siblings", &self.bury_siblings)
+            .field("auth_user", &self.auth_user)
+            .field("auth_password", &self.auth_password.as_ref().map(|_| "[redacted]"))
+            .field("time_limit", &self.time_limit)
+            .finish()
+    }
+}
+
+impl DrillConfig {
+    /// Overlays `self` on top of `base`, preferring `self`'s fields wherever they're set.
+    fn layer_over(self, base: DrillConfig) -> DrillConfig {
+        DrillConfig {
+            card_limit: self.card_limit.or(base.card_limit),
+            new_card_limit: self.new_card_limit.or(base.new_card_limit),
+            host: self.host.or(base.host),
+            port: self.port.or(base.port),
+            open_browser: self.open_browser.or(base.open_browser),
+            answer_controls: self.answer_controls.or(base.answer_controls),
+            bury_siblings: self.bury_siblings.or(base.bury_siblings),
+            auth_user: self.auth_user.or(base.auth_user),
+            auth_password: self.auth_password.or(base.auth_password),
+            time_limit: self.time_limit.or(base.time_limit),
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct RemoteConfig {
+    pub url: Option<String>,
+    pub token: Option<String>,
+}
+
+impl fmt::Debug for RemoteConfig {
+    /// Manual impl so `token` never leaks into debug/log output.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteConfig")
+            .field("url", &self.url)
+            .field("token", &self.token.as_ref().map(|_| "[redacted]"))
+            .finish()
+    }
+}
+
+impl RemoteConfig {
+    /// Resolves the auth token, preferring the `HASHCARDS_TOKEN` environment
+    /// variable over the `token` configured in `hashcards.toml`.
+    pub fn resolve_token(&self) -> Option<String> {
+        std::env::var("HASHCARDS_TOKEN")
+            .ok()
+            .or_else(|| self.token.clone())
+    }
 }
 
 pub fn load_config(directory: &Path) -> Fallible<Config> {
@@ -36,3 +106,83 @@ pub fn load_config(directory: &Path) -> Fallible<Config> {
         .map_err(|e| ErrorReport::new(format!("failed to parse {}: {}", CONFIG_FILENAME, e)))?;
     Ok(config)
 }
+
+/// Resolves the effective `[drill]` settings for `profile`, layering the named
+/// `[profile.<name>]` section, when a name is given, over the base `[drill]` section.
+/// Errors if no profile of that name exists.
+pub fn resolve_drill_profile(config: Config, profile: Option<&str>) -> Fallible<DrillConfig> {
+    match profile {
+        Some(name) => {
+            let profile_config = config
+                .profile
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ErrorReport::new(format!("no such profile: {name}")))?;
+            Ok(profile_config.layer_over(config.drill))
+        }
+        None => Ok(config.drill),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_drill_profile_without_a_name_returns_the_base_drill_config() {
+        let mut config = Config::default();
+        config.drill.port = Some(9000);
+        let resolved = resolve_drill_profile(config, None).unwrap();
+        assert_eq!(resolved.port, Some(9000));
+    }
+
+    #[test]
+    fn resolve_drill_profile_layers_the_named_profile_over_the_base() {
+        let mut config = Config::default();
+        config.drill.port = Some(8000);
+        config.drill.host = Some("127.0.0.1".to_string());
+        config.profile.insert(
+            "work".to_string(),
+            DrillConfig {
+                port: Some(9000),
+                ..Default::default()
+            },
+        );
+        let resolved = resolve_drill_profile(config, Some("work")).unwrap();
+        // The profile's own field wins...
+        assert_eq!(resolved.port, Some(9000));
+        // ...and fields the profile doesn't set fall back to the base config.
+        assert_eq!(resolved.host, Some("127.0.0.1".to_string()));
+    }
+
+    #[test]
+    fn resolve_drill_profile_errors_on_an_unknown_profile() {
+        let config = Config::default();
+        assert!(resolve_drill_profile(config, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn drill_config_debug_redacts_auth_password() {
+        let config = DrillConfig {
+            auth_user: Some("alice".to_string()),
+            auth_password: Some("hunter2".to_string()),
+            ..Default::default()
+        };
+        let debug = format!("{config:?}");
+        assert!(debug.contains("alice"));
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("[redacted]"));
+    }
+
+    #[test]
+    fn remote_config_debug_redacts_token() {
+        let config = RemoteConfig {
+            url: Some("https://example.com".to_string()),
+            token: Some("s3cr3t".to_string()),
+        };
+        let debug = format!("{config:?}");
+        assert!(debug.contains("example.com"));
+        assert!(!debug.contains("s3cr3t"));
+        assert!(debug.contains("[redacted]"));
+    }
+}